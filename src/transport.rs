@@ -0,0 +1,277 @@
+use libftd2xx::{Ft2232h, FtdiCommon, FtStatus};
+use serialport::SerialPort;
+use std::cell::RefCell;
+use std::io;
+use std::time::{Duration, Instant};
+
+/// Busy-spins until `duration` has elapsed, in the style of artiq-zynq's
+/// `spin_us`: precise enough for the microsecond-scale RS485 turnaround gap,
+/// where sleeping via the OS scheduler would overshoot.
+pub fn spin_us(duration: Duration) {
+    let start = Instant::now();
+    while start.elapsed() < duration {
+        std::hint::spin_loop();
+    }
+}
+
+/// Computes the guard interval to hold the bus idle during a half-duplex
+/// turnaround: `turnaround_bits` bit-times at `baud_rate`.
+pub fn turnaround_guard_interval(baud_rate: u32, turnaround_bits: u32) -> Duration {
+    let bit_time_secs = 1.0 / baud_rate as f64;
+    Duration::from_secs_f64(bit_time_secs * turnaround_bits as f64)
+}
+
+/// Common interface for any RS485-capable physical or virtual link.
+///
+/// `rs485_send`/`rs485_receive` are generic over this trait rather than any
+/// one vendor's API, so line coding and packet framing can be exercised
+/// against an FTDI/MPSSE device, a plain USB-RS485 dongle, or an in-memory
+/// loopback without changing a single line of the send/receive logic.
+pub trait Rs485Transport {
+    type Error: std::fmt::Debug;
+
+    /// Opens (or re-opens) the underlying link.
+    fn open(&mut self) -> Result<(), Self::Error>;
+
+    /// Writes raw bytes to the link, returning the number of bytes written.
+    fn write(&mut self, data: &[u8]) -> Result<usize, Self::Error>;
+
+    /// Writes `bufs` to the link as if they were one contiguous buffer,
+    /// without requiring the caller to concatenate them first. The default
+    /// implementation writes each slice in turn; implementations backed by a
+    /// framed buffer (e.g. one that strips a leading command header) should
+    /// override this so the framing still sees the whole packet at once.
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> Result<usize, Self::Error> {
+        let mut total = 0;
+        for buf in bufs {
+            total += self.write(buf)?;
+        }
+        Ok(total)
+    }
+
+    /// Reads raw bytes from the link, returning the number of bytes read.
+    fn read(&mut self, data: &mut [u8]) -> Result<usize, Self::Error>;
+
+    /// Switches the half-duplex driver direction: `true` drives the bus
+    /// (transmit enabled), `false` releases it (receive enabled).
+    fn set_direction(&mut self, transmit: bool) -> Result<(), Self::Error>;
+}
+
+/// FT2232H/MPSSE transport, wrapping an already-initialized `Ft2232h` handle.
+pub struct Ft2232hTransport {
+    inner: Ft2232h,
+    /// GPIO bit mask (on the MPSSE low byte) wired to the transceiver's DE/RE
+    /// driver-enable pin.
+    gpio_mask: u8,
+}
+
+impl Ft2232hTransport {
+    pub fn new(inner: Ft2232h, gpio_mask: u8) -> Self {
+        Self { inner, gpio_mask }
+    }
+}
+
+impl Rs485Transport for Ft2232hTransport {
+    type Error = FtStatus;
+
+    fn open(&mut self) -> Result<(), Self::Error> {
+        Ok(()) // Device is opened and MPSSE-initialized by init_ftdi_rs485.
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        self.inner.write(data)
+    }
+
+    fn read(&mut self, data: &mut [u8]) -> Result<usize, Self::Error> {
+        self.inner.read(data)
+    }
+
+    fn set_direction(&mut self, transmit: bool) -> Result<(), Self::Error> {
+        // MPSSE "set data bits low byte" command (0x80): value byte, then
+        // direction byte (1 = output). Drive the DE/RE pin high to enable
+        // the transmitter, low to release the bus to the receiver.
+        let value = if transmit { self.gpio_mask } else { 0 };
+        self.inner
+            .write(&[0x80, value, self.gpio_mask])
+            .map(|_| ())
+    }
+}
+
+/// Transport for ordinary USB-RS485 dongles exposed as a plain serial port.
+pub struct SerialPortTransport {
+    inner: Box<dyn SerialPort>,
+}
+
+impl SerialPortTransport {
+    pub fn open(path: &str, baud_rate: u32) -> io::Result<Self> {
+        let inner = serialport::new(path, baud_rate)
+            .timeout(std::time::Duration::from_millis(100))
+            .open()
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, format!("serialport error: {:?}", e)))?;
+        Ok(Self { inner })
+    }
+}
+
+impl Rs485Transport for SerialPortTransport {
+    type Error = io::Error;
+
+    fn open(&mut self) -> Result<(), Self::Error> {
+        Ok(()) // Port is already open once constructed.
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        io::Write::write(&mut self.inner, data)
+    }
+
+    fn read(&mut self, data: &mut [u8]) -> Result<usize, Self::Error> {
+        io::Read::read(&mut self.inner, data)
+    }
+
+    fn set_direction(&mut self, transmit: bool) -> Result<(), Self::Error> {
+        // Most USB-RS485 dongles wire DE/RE to RTS.
+        self.inner.write_request_to_send(transmit)
+    }
+}
+
+/// In-memory loopback transport: everything written after each packet's
+/// leading 3-byte MPSSE command header becomes readable back out.
+///
+/// First-class (not `#[cfg(test)]`-gated), so a full
+/// `rs485_send`/`rs485_receive` round trip can be exercised without any
+/// hardware attached.
+#[derive(Default)]
+pub struct LoopbackTransport {
+    buffer: RefCell<Vec<u8>>,
+}
+
+impl LoopbackTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Rs485Transport for LoopbackTransport {
+    type Error = io::Error;
+
+    fn open(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn write(&mut self, data: &[u8]) -> Result<usize, Self::Error> {
+        if data.len() < 3 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "packet smaller than the MPSSE command header",
+            ));
+        }
+
+        // Mirror real FTDI hardware: the first 3 bytes are the MPSSE write
+        // command consumed by the chip, not data placed on the wire.
+        self.buffer.borrow_mut().extend_from_slice(&data[3..]);
+        Ok(data.len())
+    }
+
+    fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> Result<usize, Self::Error> {
+        let total_len: usize = bufs.iter().map(|buf| buf.len()).sum();
+        if total_len < 3 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "packet smaller than the MPSSE command header",
+            ));
+        }
+
+        // Strip the leading 3-byte header across slice boundaries, the same
+        // way `write` strips it from a single contiguous packet.
+        let mut buffer = self.buffer.borrow_mut();
+        let mut header_left = 3;
+        for buf in bufs {
+            if header_left >= buf.len() {
+                header_left -= buf.len();
+                continue;
+            }
+            buffer.extend_from_slice(&buf[header_left..]);
+            header_left = 0;
+        }
+        Ok(total_len)
+    }
+
+    fn read(&mut self, data: &mut [u8]) -> Result<usize, Self::Error> {
+        let mut buffer = self.buffer.borrow_mut();
+
+        let len = buffer.len().min(data.len());
+        if len == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "no data available",
+            ));
+        }
+
+        data[..len].copy_from_slice(&buffer[..len]);
+        buffer.drain(..len);
+        Ok(len)
+    }
+
+    fn set_direction(&mut self, _transmit: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turnaround_guard_interval() {
+        // 1 Mbps → 1 us bit time; 8 bit-times of guard is 8 us.
+        let guard = turnaround_guard_interval(1_000_000, 8);
+        assert_eq!(guard, Duration::from_micros(8));
+    }
+
+    #[test]
+    fn test_spin_us_waits_at_least_the_requested_duration() {
+        let guard = Duration::from_micros(50);
+        let start = Instant::now();
+        spin_us(guard);
+        assert!(start.elapsed() >= guard);
+    }
+
+    #[test]
+    fn test_loopback_write_read_round_trip() {
+        let mut transport = LoopbackTransport::new();
+        let packet = vec![0x19, 0x01, 0x00, 0xA5, 0x5A];
+
+        transport.write(&packet).expect("loopback write failed");
+
+        let mut out = vec![0u8; 2];
+        let n = transport.read(&mut out).expect("loopback read failed");
+
+        assert_eq!(n, 2);
+        assert_eq!(out, vec![0xA5, 0x5A]);
+    }
+
+    #[test]
+    fn test_loopback_write_vectored_strips_header_across_slices() {
+        let mut transport = LoopbackTransport::new();
+        let header = [0x19, 0x01, 0x00];
+        let payload = [0xA5, 0x5A];
+
+        transport
+            .write_vectored(&[io::IoSlice::new(&header), io::IoSlice::new(&payload)])
+            .expect("loopback write_vectored failed");
+
+        let mut out = vec![0u8; 2];
+        let n = transport.read(&mut out).expect("loopback read failed");
+
+        assert_eq!(n, 2);
+        assert_eq!(out, vec![0xA5, 0x5A]);
+    }
+
+    #[test]
+    fn test_loopback_read_empty_is_eof() {
+        let mut transport = LoopbackTransport::new();
+        let mut out = vec![0u8; 4];
+
+        let err = transport.read(&mut out).expect_err("expected no data");
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}